@@ -0,0 +1,200 @@
+// src-tauri/src/backup.rs
+//
+// A safety net for destructive `.ma`/`.mb` cleans: every file about to be
+// cleaned in "scene" or "directory" mode is copied into a timestamped
+// backup folder first, with the copy recorded in a persistent manifest so
+// the UI can show prior cleaning sessions and roll a file back.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::CommandError;
+
+// Disambiguates backups taken within the same second so two same-named
+// files (e.g. `a/scene.ma` and `b/scene.ma`) backed up in one directory
+// clean never collide on backup path or manifest id.
+static BACKUP_SEQ: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupEntry {
+    pub id: String,
+    pub original_path: String,
+    pub backup_path: String,
+    pub timestamp: u64,
+    pub operations: Vec<String>,
+}
+
+fn data_root() -> Result<PathBuf, CommandError> {
+    // Overridable so tests (and power users) aren't forced onto the real OS
+    // data directory.
+    if let Ok(dir) = std::env::var("MAYACLEANER_DATA_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+
+    let dir = dirs::data_dir()
+        .ok_or_else(|| CommandError::Config("Could not resolve OS data directory".to_string()))?
+        .join("MayaFileCleaner");
+
+    Ok(dir)
+}
+
+fn manifest_path() -> Result<PathBuf, CommandError> {
+    Ok(data_root()?.join("backups.json"))
+}
+
+fn load_manifest() -> Result<Vec<BackupEntry>, CommandError> {
+    let path = manifest_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(&path)?;
+    if contents.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(serde_json::from_str(&contents)?)
+}
+
+fn save_manifest(entries: &[BackupEntry]) -> Result<(), CommandError> {
+    let path = manifest_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(&path, serde_json::to_string_pretty(entries)?)?;
+    Ok(())
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// Copy `original` into a timestamped backup folder and append a manifest
+// entry recording the operations that are about to be run against it. Each
+// call gets its own numbered subfolder so same-named files from different
+// directories never overwrite one another's backup.
+pub fn backup_file(original: &Path, operations: &[String]) -> Result<BackupEntry, CommandError> {
+    let timestamp = unix_timestamp();
+    let seq = BACKUP_SEQ.fetch_add(1, Ordering::SeqCst);
+
+    let session_dir = data_root()?
+        .join("backups")
+        .join(timestamp.to_string())
+        .join(seq.to_string());
+    fs::create_dir_all(&session_dir)?;
+
+    let file_name = original
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "scene".to_string());
+    let backup_path = session_dir.join(&file_name);
+    fs::copy(original, &backup_path)?;
+
+    let entry = BackupEntry {
+        id: format!("{}-{}", timestamp, seq),
+        original_path: original.to_string_lossy().to_string(),
+        backup_path: backup_path.to_string_lossy().to_string(),
+        timestamp,
+        operations: operations.to_vec(),
+    };
+
+    let mut entries = load_manifest()?;
+    entries.push(entry.clone());
+    save_manifest(&entries)?;
+
+    Ok(entry)
+}
+
+// List every recorded backup, most recent first, for the UI's history view.
+pub fn list_backups() -> Result<Vec<BackupEntry>, CommandError> {
+    let mut entries = load_manifest()?;
+    entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(entries)
+}
+
+// Copy a backup back over its original location.
+pub fn restore_backup(entry_id: &str) -> Result<(), CommandError> {
+    let entries = load_manifest()?;
+    let entry = entries
+        .iter()
+        .find(|e| e.id == entry_id)
+        .ok_or_else(|| CommandError::BackupNotFound(entry_id.to_string()))?;
+
+    fs::copy(&entry.backup_path, &entry.original_path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // `data_root` reads a process-wide env var, so tests that touch it must
+    // not run concurrently with each other.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    fn with_temp_data_dir<T>(f: impl FnOnce(&Path) -> T) -> T {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let dir = std::env::temp_dir().join(format!(
+            "mfc_backup_test_{}_{}",
+            std::process::id(),
+            BACKUP_SEQ.fetch_add(1, Ordering::SeqCst)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        std::env::set_var("MAYACLEANER_DATA_DIR", &dir);
+
+        let result = f(&dir);
+
+        std::env::remove_var("MAYACLEANER_DATA_DIR");
+        let _ = fs::remove_dir_all(&dir);
+        result
+    }
+
+    #[test]
+    fn same_named_files_in_different_dirs_do_not_collide() {
+        with_temp_data_dir(|dir| {
+            let dir_a = dir.join("a");
+            let dir_b = dir.join("b");
+            fs::create_dir_all(&dir_a).unwrap();
+            fs::create_dir_all(&dir_b).unwrap();
+
+            let file_a = dir_a.join("scene.ma");
+            let file_b = dir_b.join("scene.ma");
+            fs::write(&file_a, b"scene A").unwrap();
+            fs::write(&file_b, b"scene B").unwrap();
+
+            let entry_a = backup_file(&file_a, &[]).unwrap();
+            let entry_b = backup_file(&file_b, &[]).unwrap();
+
+            assert_ne!(entry_a.id, entry_b.id);
+            assert_ne!(entry_a.backup_path, entry_b.backup_path);
+            assert_eq!(fs::read(&entry_a.backup_path).unwrap(), b"scene A");
+            assert_eq!(fs::read(&entry_b.backup_path).unwrap(), b"scene B");
+        });
+    }
+
+    #[test]
+    fn restore_backup_round_trips() {
+        with_temp_data_dir(|dir| {
+            let file = dir.join("scene.ma");
+            fs::write(&file, b"original").unwrap();
+
+            let entry = backup_file(&file, &["unknown_nodes".to_string()]).unwrap();
+            fs::write(&file, b"cleaned").unwrap();
+            assert_eq!(fs::read(&file).unwrap(), b"cleaned");
+
+            restore_backup(&entry.id).unwrap();
+            assert_eq!(fs::read(&file).unwrap(), b"original");
+
+            let listed = list_backups().unwrap();
+            assert!(listed.iter().any(|e| e.id == entry.id));
+        });
+    }
+}
@@ -0,0 +1,492 @@
+// src-tauri/src/cleaner.rs
+//
+// Core logic for locating mayapy and driving `utils.py`. Shared by the Tauri
+// commands in `main.rs` (which stream progress as events and support
+// cancellation) and the headless CLI in `cli.rs` (which has no app handle).
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+
+use crate::backup;
+use crate::config::Config;
+use crate::error::CommandError;
+
+// Holds the in-flight cleaner child process plus an explicit cancellation
+// flag. The flag (not the presence/absence of the `Child`) is what
+// distinguishes "killed by `cancel`" from "finished on its own just before
+// `cancel` ran" — otherwise a cancel that races a successful finish can
+// make `run_utils` report a completed run as cancelled.
+#[derive(Default)]
+pub struct ChildSlot {
+    child: Mutex<Option<Child>>,
+    cancelled: AtomicBool,
+}
+
+impl ChildSlot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn set(&self, child: Child) {
+        self.cancelled.store(false, Ordering::SeqCst);
+        *self.child.lock().unwrap() = Some(child);
+    }
+
+    // Kill the running child and mark the slot cancelled, but only if a
+    // child is actually still sitting in the slot and has not already
+    // exited. Setting `cancelled` happens inside the same locked section as
+    // the kill so a `run_utils` that has already `take()`n the child
+    // (finished on its own) can never be reclassified as cancelled after
+    // the fact.
+    pub fn cancel(&self) -> Result<(), CommandError> {
+        let mut guard = self.child.lock().unwrap();
+        if let Some(child) = guard.as_mut() {
+            if child.try_wait()?.is_some() {
+                return Ok(());
+            }
+            self.cancelled.store(true, Ordering::SeqCst);
+            child.kill()?;
+        }
+        Ok(())
+    }
+}
+
+// Bump whenever a field is added/removed/retyped so `parse_cleaning_result`
+// knows how to upgrade output from an older `utils.py`.
+pub const CLEANING_RESULT_SCHEMA_VERSION: u32 = 1;
+
+fn default_schema_version() -> u32 {
+    0
+}
+
+// Define the result structure that matches our Python script output. Kept
+// schema-versioned so an app built against a newer/older `utils.py` doesn't
+// hard-fail on `serde_json::from_str` when the two drift out of sync.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CleaningResult {
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    #[serde(default)]
+    pub mode: String,
+    pub status: String,
+    pub message: String,
+    pub details: Vec<String>,
+    pub cleaned_count: u32,
+    pub processed_count: u32,
+}
+
+// Parse `utils.py`'s JSON output, tolerating a drifted or pre-versioning
+// schema instead of failing outright: a straight deserialize covers the
+// common case (missing `schema_version`/`mode` fall back to their serde
+// defaults above), and a looser field-by-field read is the fallback for
+// anything more malformed than that.
+fn parse_cleaning_result(json: &str) -> Result<CleaningResult, CommandError> {
+    if let Ok(mut result) = serde_json::from_str::<CleaningResult>(json) {
+        if result.schema_version < CLEANING_RESULT_SCHEMA_VERSION {
+            result.schema_version = CLEANING_RESULT_SCHEMA_VERSION;
+        }
+        return Ok(result);
+    }
+
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    Ok(CleaningResult {
+        schema_version: CLEANING_RESULT_SCHEMA_VERSION,
+        mode: value.get("mode").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+        status: value.get("status").and_then(|v| v.as_str()).unwrap_or("unknown").to_string(),
+        message: value
+            .get("message")
+            .and_then(|v| v.as_str())
+            .unwrap_or("utils.py returned a result in an unrecognized format")
+            .to_string(),
+        details: value
+            .get("details")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|e| e.as_str().map(str::to_string)).collect())
+            .unwrap_or_default(),
+        cleaned_count: value.get("cleaned_count").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
+        processed_count: value.get("processed_count").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
+    })
+}
+
+// One line of progress emitted by utils.py while it processes a directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanProgress {
+    pub path: String,
+    pub action: String,
+    pub status: String,
+}
+
+// Build the ordered list of mayapy candidates we're willing to check:
+// explicit overrides first, then platform-specific default install roots.
+pub fn maya_exe_candidates(config: &Config) -> Vec<String> {
+    let mut candidates = Vec::new();
+
+    // A path saved in maya_cleaner.toml is the most explicit override available.
+    if let Some(path) = &config.mayapy_path {
+        candidates.push(path.clone());
+    }
+
+    // Explicit overrides take priority over any scan.
+    if let Ok(path) = env::var("MAYACLEANER_MAYAPY") {
+        candidates.push(path);
+    }
+    if let Ok(location) = env::var("MAYA_LOCATION") {
+        let mayapy = if cfg!(target_os = "windows") { "bin/mayapy.exe" } else { "bin/mayapy" };
+        candidates.push(format!("{}/{}", location.trim_end_matches('/'), mayapy));
+    }
+
+    // Platform-specific default install roots (newest versions first).
+    for year in (2020..=2025).rev() {
+        if cfg!(target_os = "windows") {
+            candidates.push(format!("C:\\Program Files\\Autodesk\\Maya{}\\bin\\mayapy.exe", year));
+        } else if cfg!(target_os = "macos") {
+            candidates.push(format!("/Applications/Autodesk/maya{}/Maya.app/Contents/bin/mayapy", year));
+        } else {
+            candidates.push(format!("/usr/autodesk/maya{}/bin/mayapy", year));
+        }
+    }
+
+    // Expand `~` and shell variables (e.g. $HOME) in every candidate.
+    candidates
+        .into_iter()
+        .map(|c| shellexpand::full(&c).map(|s| s.into_owned()).unwrap_or(c))
+        .collect()
+}
+
+// Scan the candidate list for an existing mayapy binary, with no caching.
+pub fn resolve_maya_exe(config: &Config) -> Result<String, CommandError> {
+    let candidates = maya_exe_candidates(config);
+
+    for location in &candidates {
+        if Path::new(location).exists() {
+            return Ok(location.clone());
+        }
+    }
+
+    Err(CommandError::MayaNotFound(candidates))
+}
+
+// Recursively collect every `.ma`/`.mb` file under `dir`, skipping anything
+// matching one of `ignore_patterns` (glob patterns matched against the
+// file name, e.g. `*_backup.*`).
+fn collect_maya_files(dir: &Path, ignore_patterns: &[String]) -> Vec<PathBuf> {
+    let patterns: Vec<glob::Pattern> = ignore_patterns
+        .iter()
+        .filter_map(|p| glob::Pattern::new(p).ok())
+        .collect();
+
+    let mut files = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return files;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+
+        if patterns.iter().any(|p| p.matches(&name)) {
+            continue;
+        }
+
+        if path.is_dir() {
+            files.extend(collect_maya_files(&path, ignore_patterns));
+        } else {
+            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+            if ext == "ma" || ext == "mb" {
+                files.push(path);
+            }
+        }
+    }
+
+    files
+}
+
+// Back up every file the upcoming run is about to touch, per `config.create_backups`.
+fn backup_targets(mode: &str, path: Option<&str>, config: &Config) -> Result<(), CommandError> {
+    if !config.create_backups {
+        return Ok(());
+    }
+
+    let targets: Vec<PathBuf> = match (mode, path) {
+        ("scene", Some(p)) => vec![PathBuf::from(p)],
+        ("directory", Some(p)) => collect_maya_files(Path::new(p), &config.ignore_patterns),
+        _ => Vec::new(),
+    };
+
+    for target in targets {
+        backup::backup_file(&target, &config.enabled_operations)?;
+    }
+
+    Ok(())
+}
+
+fn locate_script() -> Result<PathBuf, CommandError> {
+    let current_exe = env::current_exe()?;
+    let exe_dir = current_exe
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_default();
+
+    let script_path = exe_dir.join("utils.py");
+    if !script_path.exists() {
+        return Err(CommandError::ScriptMissing(script_path));
+    }
+
+    Ok(script_path)
+}
+
+// Run `utils.py` with Maya Python, streaming per-file progress to
+// `on_progress` as it goes instead of blocking until the whole run finishes.
+// `child_slot`, when given, is populated with the running child so a
+// concurrent `cancel_cleaning` call can kill it.
+pub fn run_utils(
+    mode: &str,
+    path: Option<&str>,
+    maya_exe: &str,
+    config: &Config,
+    child_slot: Option<&ChildSlot>,
+    mut on_progress: impl FnMut(CleanProgress),
+) -> Result<CleaningResult, CommandError> {
+    backup_targets(mode, path, config)?;
+
+    let script_path = locate_script()?;
+
+    // Temporary files for results and logs
+    let temp_dir = env::temp_dir();
+    let results_file = temp_dir.join("maya_cleaner_results.json");
+    let log_file = temp_dir.join("maya_cleaner_log.txt");
+
+    // Build the command
+    let mut cmd = Command::new(maya_exe);
+    cmd.arg(&script_path)
+       .arg("--mode")
+       .arg(mode)
+       .arg("--log")
+       .arg(&log_file)
+       .arg("--json")
+       .arg(&results_file);
+
+    // Add path if provided
+    if let Some(p) = path {
+        cmd.arg("--path").arg(p);
+    }
+
+    // Thread the persisted config through as additional `--` flags so
+    // behavior can be tuned from the settings panel without touching utils.py.
+    if !config.enabled_operations.is_empty() {
+        cmd.arg("--ops").arg(config.enabled_operations.join(","));
+    }
+    for pattern in &config.ignore_patterns {
+        cmd.arg("--ignore").arg(pattern);
+    }
+    if config.create_backups {
+        cmd.arg("--backup");
+    }
+
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    println!("Running command: {:?}", cmd);
+
+    // Spawn instead of `.output()` so we can stream stdout as it arrives
+    // and keep a handle around for cancellation. When the caller doesn't
+    // care about cancellation (the CLI), fall back to a slot of our own.
+    let local_slot;
+    let slot: &ChildSlot = match child_slot {
+        Some(slot) => slot,
+        None => {
+            local_slot = ChildSlot::new();
+            &local_slot
+        }
+    };
+
+    let mut child = cmd.spawn()?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    slot.set(child);
+
+    // Drain stderr on its own thread, concurrently with the stdout loop
+    // below. Maya is noisy on stderr; if we read the two pipes
+    // sequentially and utils.py fills the stderr pipe buffer while we're
+    // still blocked waiting on stdout, the child blocks on its stderr
+    // write and we deadlock waiting for a stdout EOF that never comes.
+    // `Command::output()` avoids exactly this by draining both
+    // concurrently, so we do the same here.
+    let stderr_handle = stderr.map(|mut stderr| {
+        thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = stderr.read_to_string(&mut buf);
+            buf
+        })
+    });
+
+    // Read each JSON line utils.py emits for a processed file.
+    if let Some(stdout) = stdout {
+        let reader = BufReader::new(stdout);
+        for line in reader.lines().map_while(Result::ok) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Ok(progress) = serde_json::from_str::<CleanProgress>(&line) {
+                on_progress(progress);
+            }
+        }
+    }
+
+    let stderr_output = stderr_handle
+        .and_then(|handle| handle.join().ok())
+        .unwrap_or_default();
+
+    // The child is always still here to reap, whether it finished on its
+    // own or `cancel` killed it — `slot.cancelled` (not its presence) is
+    // what tells the two apart.
+    let Some(mut child) = slot.child.lock().unwrap().take() else {
+        return Err(CommandError::ResultsMissing);
+    };
+
+    let status = child.wait()?;
+
+    if slot.cancelled.load(Ordering::SeqCst) {
+        return Err(CommandError::Cancelled);
+    }
+
+    if !status.success() {
+        // Read the log file if available
+        let log = if log_file.exists() {
+            fs::read_to_string(&log_file).ok()
+        } else {
+            None
+        };
+        let stderr = if let Some(log) = &log {
+            log.clone()
+        } else {
+            stderr_output
+        };
+
+        return Err(CommandError::PythonExecutionFailed { stderr, log });
+    }
+
+    // Read the results JSON
+    if !results_file.exists() {
+        return Err(CommandError::ResultsMissing);
+    }
+
+    let results_json = fs::read_to_string(&results_file)?;
+    parse_cleaning_result(&results_json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `maya_exe_candidates` reads process-wide env vars, so tests touching
+    // them must not run concurrently with each other.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn candidate_order_prefers_config_then_env_overrides() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        std::env::remove_var("MAYACLEANER_MAYAPY");
+        std::env::remove_var("MAYA_LOCATION");
+
+        let mut config = Config::default();
+        config.mayapy_path = Some("/config/mayapy".to_string());
+        std::env::set_var("MAYACLEANER_MAYAPY", "/env/mayapy");
+        std::env::set_var("MAYA_LOCATION", "/env/maya_location");
+
+        let candidates = maya_exe_candidates(&config);
+
+        std::env::remove_var("MAYACLEANER_MAYAPY");
+        std::env::remove_var("MAYA_LOCATION");
+
+        let expected_mayapy_bin = if cfg!(target_os = "windows") { "bin/mayapy.exe" } else { "bin/mayapy" };
+        assert_eq!(candidates[0], "/config/mayapy");
+        assert_eq!(candidates[1], "/env/mayapy");
+        assert_eq!(candidates[2], format!("/env/maya_location/{}", expected_mayapy_bin));
+    }
+
+    #[test]
+    fn pre_versioning_output_is_upgraded_to_the_current_schema() {
+        // A `utils.py` from before `schema_version`/`mode` existed still
+        // deserializes cleanly via `#[serde(default)]`, and should come back
+        // bumped to the current schema version rather than stuck at 0.
+        let json = r#"{
+            "status": "success",
+            "message": "Cleaned 2 files",
+            "details": ["removed unknown_nodes"],
+            "cleaned_count": 2,
+            "processed_count": 2
+        }"#;
+
+        let result = parse_cleaning_result(json).unwrap();
+
+        assert_eq!(result.schema_version, CLEANING_RESULT_SCHEMA_VERSION);
+        assert_eq!(result.mode, "");
+        assert_eq!(result.status, "success");
+        assert_eq!(result.cleaned_count, 2);
+    }
+
+    #[test]
+    fn unparseable_output_falls_back_to_field_by_field_extraction() {
+        // A result shaped so differently it can't deserialize directly
+        // (`cleaned_count` as a string) still yields a usable result instead
+        // of a hard failure.
+        let json = r#"{
+            "status": "success",
+            "message": "ok",
+            "cleaned_count": "not-a-number"
+        }"#;
+
+        let result = parse_cleaning_result(json).unwrap();
+
+        assert_eq!(result.schema_version, CLEANING_RESULT_SCHEMA_VERSION);
+        assert_eq!(result.status, "success");
+        assert_eq!(result.message, "ok");
+        assert_eq!(result.cleaned_count, 0);
+        assert!(result.details.is_empty());
+    }
+
+    #[test]
+    fn garbage_output_falls_back_to_an_unknown_status() {
+        let result = parse_cleaning_result(r#"{"unexpected": true}"#).unwrap();
+
+        assert_eq!(result.status, "unknown");
+        assert_eq!(result.message, "utils.py returned a result in an unrecognized format");
+    }
+
+    #[test]
+    fn collect_maya_files_skips_ignored_names_and_recurses() {
+        let dir = std::env::temp_dir().join(format!(
+            "mfc_collect_test_{}_{}",
+            std::process::id(),
+            std::process::id()
+        ));
+        let sub = dir.join("sub");
+        fs::create_dir_all(&sub).unwrap();
+
+        fs::write(dir.join("scene.ma"), b"").unwrap();
+        fs::write(dir.join("scene_backup.ma"), b"").unwrap();
+        fs::write(dir.join("notes.txt"), b"").unwrap();
+        fs::write(sub.join("other.mb"), b"").unwrap();
+
+        let found = collect_maya_files(&dir, &["*_backup.*".to_string()]);
+        let _ = fs::remove_dir_all(&dir);
+
+        let names: Vec<String> = found
+            .iter()
+            .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            .collect();
+
+        assert_eq!(found.len(), 2);
+        assert!(names.contains(&"scene.ma".to_string()));
+        assert!(names.contains(&"other.mb".to_string()));
+        assert!(!names.contains(&"scene_backup.ma".to_string()));
+    }
+}
@@ -0,0 +1,128 @@
+// src-tauri/src/cli.rs
+//
+// Headless entry point so studios can wire the cleaner into render-farm
+// submission scripts and pre-commit hooks without launching the windowed app.
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, Subcommand};
+
+use crate::cleaner;
+use crate::config;
+use crate::error::CommandError;
+
+#[derive(Parser)]
+#[command(name = "maya-file-cleaner", about = "Clean unwanted nodes, references and unknown plugins from Maya scene files")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Clean a single .ma/.mb scene file
+    Scene { file: PathBuf },
+    /// Clean every Maya file in a directory
+    Directory { dir: PathBuf },
+    /// Clean the current user's Maya preference and cache directories
+    User,
+    /// List the available cleaning modes
+    List,
+}
+
+// Parses argv and, if a subcommand was given, runs it to completion and
+// returns the process exit code. Returns `None` when invoked with no
+// subcommand so `main` falls through to launching the windowed app.
+pub fn try_run_headless() -> Option<i32> {
+    let cli = Cli::parse();
+
+    let command = cli.command?;
+
+    let exit_code = match command {
+        Commands::List => {
+            print_modes();
+            0
+        }
+        Commands::Scene { file } => match validate_scene(&file) {
+            Ok(()) => run("scene", Some(file.to_string_lossy().as_ref())),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                1
+            }
+        },
+        Commands::Directory { dir } => match validate_directory(&dir) {
+            Ok(()) => run("directory", Some(dir.to_string_lossy().as_ref())),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                1
+            }
+        },
+        Commands::User => run("user", None),
+    };
+
+    Some(exit_code)
+}
+
+// Mirrors the validation `clean_maya_scene` does in `main.rs` so the CLI and
+// GUI front ends report the same `InvalidPath`/`NotAMayaFile` errors for the
+// same bad input, instead of the CLI surfacing a generic I/O error later.
+fn validate_scene(file: &Path) -> Result<(), CommandError> {
+    if !file.exists() {
+        return Err(CommandError::InvalidPath(file.to_string_lossy().to_string()));
+    }
+
+    let ext = file.extension().and_then(|e| e.to_str()).unwrap_or("");
+    if ext.to_lowercase() != "ma" && ext.to_lowercase() != "mb" {
+        return Err(CommandError::NotAMayaFile(file.to_string_lossy().to_string()));
+    }
+
+    Ok(())
+}
+
+// Mirrors the validation `clean_maya_directory` does in `main.rs`.
+fn validate_directory(dir: &Path) -> Result<(), CommandError> {
+    if !dir.exists() || !dir.is_dir() {
+        return Err(CommandError::InvalidPath(dir.to_string_lossy().to_string()));
+    }
+
+    Ok(())
+}
+
+fn print_modes() {
+    println!("Available cleaning modes:");
+    println!("  scene <file>   Clean a single .ma/.mb scene file");
+    println!("  directory <dir> Clean every Maya file in a directory");
+    println!("  user           Clean the current user's Maya preference and cache directories");
+}
+
+fn run(mode: &str, path: Option<&str>) -> i32 {
+    let config = config::load_or_init().unwrap_or_else(|e| {
+        eprintln!("Warning: Could not load config, using defaults: {}", e);
+        config::Config::default()
+    });
+
+    let maya_exe = match cleaner::resolve_maya_exe(&config) {
+        Ok(exe) => exe,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    };
+
+    let result = cleaner::run_utils(mode, path, &maya_exe, &config, None, |progress| {
+        println!(
+            "{}",
+            serde_json::to_string(&progress).unwrap_or_else(|_| format!("{:?}", progress))
+        );
+    });
+
+    match result {
+        Ok(result) => {
+            println!("{}", serde_json::to_string_pretty(&result).unwrap_or_default());
+            if result.status == "success" { 0 } else { 1 }
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
@@ -0,0 +1,73 @@
+// src-tauri/src/config.rs
+use std::fs;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+
+use crate::error::CommandError;
+
+const CONFIG_FILE_NAME: &str = "maya_cleaner.toml";
+
+// User-editable settings, persisted as TOML in the OS config directory.
+// Loaded once at startup and cached in `AppState` so the frontend can
+// read/write it without touching `utils.py` directly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub mayapy_path: Option<String>,
+    pub enabled_operations: Vec<String>,
+    pub ignore_patterns: Vec<String>,
+    pub create_backups: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            mayapy_path: None,
+            enabled_operations: vec![
+                "unknown_nodes".to_string(),
+                "unused_plugins".to_string(),
+                "empty_groups".to_string(),
+            ],
+            ignore_patterns: vec!["*_backup.*".to_string(), "*.tmp.*".to_string()],
+            create_backups: true,
+        }
+    }
+}
+
+fn config_path() -> Result<PathBuf, CommandError> {
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| CommandError::Config("Could not resolve OS config directory".to_string()))?
+        .join("MayaFileCleaner");
+
+    Ok(config_dir.join(CONFIG_FILE_NAME))
+}
+
+// Read `maya_cleaner.toml`, creating it with defaults on first run.
+pub fn load_or_init() -> Result<Config, CommandError> {
+    let path = config_path()?;
+
+    if !path.exists() {
+        let config = Config::default();
+        save(&config)?;
+        return Ok(config);
+    }
+
+    let contents = fs::read_to_string(&path)?;
+    let config: Config = toml::from_str(&contents)
+        .map_err(|e| CommandError::Config(format!("Malformed config at {:?}: {}", path, e)))?;
+
+    Ok(config)
+}
+
+pub fn save(config: &Config) -> Result<(), CommandError> {
+    let path = config_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let contents = toml::to_string_pretty(config)
+        .map_err(|e| CommandError::Config(format!("Failed to serialize config: {}", e)))?;
+    fs::write(&path, contents)?;
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+// src-tauri/src/error.rs
+use std::path::PathBuf;
+use serde::{Serialize, Serializer};
+use serde::ser::SerializeStruct;
+use thiserror::Error;
+
+// Structured error type shared by every Tauri command so the frontend can
+// branch on `kind` instead of pattern-matching opaque strings.
+#[derive(Debug, Error)]
+pub enum CommandError {
+    #[error("Maya Python executable (mayapy) not found. Checked: {0:?}")]
+    MayaNotFound(Vec<String>),
+
+    #[error("Cleaner script not found at: {0:?}")]
+    ScriptMissing(PathBuf),
+
+    #[error("Invalid path: {0}")]
+    InvalidPath(String),
+
+    #[error("File is not a Maya file (.ma or .mb): {0}")]
+    NotAMayaFile(String),
+
+    #[error("Maya cleaner script failed: {stderr}")]
+    PythonExecutionFailed {
+        stderr: String,
+        log: Option<String>,
+    },
+
+    #[error("Results file was not created")]
+    ResultsMissing,
+
+    #[error("Cleaning was cancelled")]
+    Cancelled,
+
+    #[error("Config error: {0}")]
+    Config(String),
+
+    #[error("No backup found with id: {0}")]
+    BackupNotFound(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl CommandError {
+    fn kind(&self) -> &'static str {
+        match self {
+            CommandError::MayaNotFound(_) => "MayaNotFound",
+            CommandError::ScriptMissing(_) => "ScriptMissing",
+            CommandError::InvalidPath(_) => "InvalidPath",
+            CommandError::NotAMayaFile(_) => "NotAMayaFile",
+            CommandError::PythonExecutionFailed { .. } => "PythonExecutionFailed",
+            CommandError::ResultsMissing => "ResultsMissing",
+            CommandError::Cancelled => "Cancelled",
+            CommandError::Config(_) => "Config",
+            CommandError::BackupNotFound(_) => "BackupNotFound",
+            CommandError::Io(_) => "Io",
+            CommandError::Json(_) => "Json",
+        }
+    }
+}
+
+// Serialized as `{ "kind": "...", "message": "..." }` so the frontend gets a
+// stable discriminant plus a human-readable message in one payload.
+impl Serialize for CommandError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("CommandError", 2)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
@@ -5,52 +5,93 @@
 )]
 
 // src-tauri/src/main.rs
-use std::process::Command;
 use std::path::Path;
 use std::fs;
 use std::env;
-use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 use std::sync::Mutex;
+use serde::Serialize;
 
-// Define the result structure that matches our Python script output
-#[derive(Debug, Serialize, Deserialize)]
-struct CleaningResult {
-    status: String,
-    message: String,
-    details: Vec<String>,
-    cleaned_count: u32,
-    processed_count: u32,
-}
+mod error;
+use error::CommandError;
+
+mod config;
+use config::Config;
+
+mod cleaner;
+use cleaner::{ChildSlot, CleaningResult};
 
-// App state to hold cached Maya executable path
+mod backup;
+use backup::BackupEntry;
+
+mod cli;
+
+// App state to hold cached Maya executable path, the loaded config, and
+// the in-flight cleaner process (if any) so it can be cancelled.
 struct AppState {
     maya_exe_path: Mutex<Option<String>>,
+    config: Mutex<Config>,
+    child: ChildSlot,
 }
 
 fn main() {
+    // A subcommand (e.g. `maya-file-cleaner scene foo.ma`) runs the cleaner
+    // headlessly and skips the windowed app entirely.
+    if let Some(exit_code) = cli::try_run_headless() {
+        std::process::exit(exit_code);
+    }
+
     // Copy the cleaner script to necessary locations
     if let Err(e) = setup_utils() {
         eprintln!("Warning: Could not setup cleaner script: {}", e);
     }
 
+    let config = config::load_or_init().unwrap_or_else(|e| {
+        eprintln!("Warning: Could not load config, using defaults: {}", e);
+        Config::default()
+    });
+
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_fs::init())
         .manage(AppState {
             maya_exe_path: Mutex::new(None),
+            config: Mutex::new(config),
+            child: ChildSlot::new(),
         })
         .invoke_handler(tauri::generate_handler![
             find_maya_exe,
+            set_maya_exe_path,
+            get_config,
+            save_config,
             clean_maya_scene,
             clean_maya_directory,
-            clean_maya_user_dirs
+            clean_maya_user_dirs,
+            preview_maya_scene,
+            preview_maya_directory,
+            cancel_cleaning,
+            list_backups,
+            restore_backup
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
+// Return the currently loaded config so the frontend can render a settings panel.
+#[tauri::command]
+fn get_config(state: State<AppState>) -> Result<Config, CommandError> {
+    Ok(state.config.lock().unwrap().clone())
+}
+
+// Persist a new config to disk and cache it for subsequent cleans.
+#[tauri::command]
+fn save_config(config: Config, state: State<AppState>) -> Result<(), CommandError> {
+    config::save(&config)?;
+    *state.config.lock().unwrap() = config;
+    Ok(())
+}
+
 // Ensure cleaner script is in both required locations
 fn setup_utils() -> Result<(), String> {
     // Source script path - Get the executable's directory
@@ -106,153 +147,161 @@ fn setup_utils() -> Result<(), String> {
     }
 }
 
-// Find Maya's Python executable (mayapy.exe)
+// The resolved mayapy path plus the full ordered candidate list that was
+// searched, so the settings panel can show the user where to drop an
+// override even when a mayapy was found on the first try.
+#[derive(Debug, Serialize)]
+struct MayaExeLookup {
+    path: String,
+    candidates: Vec<String>,
+}
+
+// Find Maya's Python executable (mayapy.exe), caching the result in AppState.
 #[tauri::command]
-fn find_maya_exe(state: State<AppState>) -> Result<String, String> {
+fn find_maya_exe(state: State<AppState>) -> Result<MayaExeLookup, CommandError> {
+    let config = state.config.lock().unwrap().clone();
+    let candidates = cleaner::maya_exe_candidates(&config);
+
     // Check if we already found the path
     {
         let cached_path = state.maya_exe_path.lock().unwrap();
         if let Some(path) = &*cached_path {
-            return Ok(path.clone());
+            return Ok(MayaExeLookup { path: path.clone(), candidates });
         }
     }
-    
-    // Common locations to check for Maya installation
-    let mut possible_locations = Vec::new();
-    
-    // Check Program Files (newest versions first)
-    for year in (2020..=2025).rev() {
-        possible_locations.push(format!("C:\\Program Files\\Autodesk\\Maya{}\\bin\\mayapy.exe", year));
-    }
-    
-    // Try to find mayapy.exe
-    for location in possible_locations {
-        if Path::new(&location).exists() {
-            // Cache the found path
-            let mut cached_path = state.maya_exe_path.lock().unwrap();
-            *cached_path = Some(location.clone());
-            
-            return Ok(location);
-        }
+
+    let found = cleaner::resolve_maya_exe(&config)?;
+
+    let mut cached_path = state.maya_exe_path.lock().unwrap();
+    *cached_path = Some(found.clone());
+
+    Ok(MayaExeLookup { path: found, candidates })
+}
+
+// Let the user point the cleaner at a mayapy install the scan couldn't find.
+#[tauri::command]
+fn set_maya_exe_path(path: String, state: State<AppState>) -> Result<String, CommandError> {
+    let expanded = shellexpand::full(&path)
+        .map(|s| s.into_owned())
+        .unwrap_or(path);
+
+    if !Path::new(&expanded).exists() {
+        return Err(CommandError::InvalidPath(expanded));
     }
-    
-    Err("Maya Python executable (mayapy.exe) not found. Please install Maya or specify the path manually.".to_string())
+
+    let mut cached_path = state.maya_exe_path.lock().unwrap();
+    *cached_path = Some(expanded.clone());
+
+    Ok(expanded)
 }
 
-// Run the cleaner script with Maya Python
+// Run the cleaner script with Maya Python, forwarding each progress line to
+// the frontend as a `clean-progress` event and registering the child so
+// `cancel_cleaning` can kill an in-flight run.
 fn run_utils(
-    mode: &str, 
-    path: Option<&str>, 
-    maya_exe: &str
-) -> Result<CleaningResult, String> {
-    // Get the executable's directory for finding the script
-    let current_exe = match env::current_exe() {
-        Ok(path) => path,
-        Err(e) => return Err(format!("Failed to get current executable path: {}", e))
-    };
-    
-    let exe_dir = match current_exe.parent() {
-        Some(path) => path.to_path_buf(),
-        None => return Err("Failed to get parent directory of executable".to_string())
-    };
-    
-    // Look for the script in the executable directory
-    let script_path = exe_dir.join("utils.py");
-    
-    if !script_path.exists() {
-        return Err(format!("Cleaner script not found at: {:?}", script_path));
-    }
-    
-    // Temporary files for results and logs
-    let temp_dir = env::temp_dir();
-    let results_file = temp_dir.join("maya_cleaner_results.json");
-    let log_file = temp_dir.join("maya_cleaner_log.txt");
-    
-    // Build the command
-    let mut cmd = Command::new(maya_exe);
-    cmd.arg(&script_path)
-       .arg("--mode")
-       .arg(mode)
-       .arg("--log")
-       .arg(&log_file)
-       .arg("--json")
-       .arg(&results_file);
-    
-    // Add path if provided
-    if let Some(p) = path {
-        cmd.arg("--path").arg(p);
-    }
-    
-    println!("Running command: {:?}", cmd);
-    
-    // Run the command
-    let output = cmd.output().map_err(|e| format!("Failed to run Maya Python: {}", e))?;
-    
-    if !output.status.success() {
-        // Read the log file if available
-        let error_message = if log_file.exists() {
-            fs::read_to_string(&log_file).unwrap_or_else(|_| {
-                String::from_utf8_lossy(&output.stderr).to_string()
-            })
-        } else {
-            String::from_utf8_lossy(&output.stderr).to_string()
-        };
-        
-        return Err(format!("Maya cleaner script failed: {}", error_message));
-    }
-    
-    // Read the results JSON
-    if !results_file.exists() {
-        return Err("Results file not created".to_string());
-    }
-    
-    let results_json = fs::read_to_string(&results_file)
-        .map_err(|e| format!("Failed to read results file: {}", e))?;
-    
-    let results: CleaningResult = serde_json::from_str(&results_json)
-        .map_err(|e| format!("Failed to parse results: {}", e))?;
-    
-    Ok(results)
+    mode: &str,
+    path: Option<&str>,
+    maya_exe: &str,
+    config: &Config,
+    app: &AppHandle,
+    state: &AppState,
+) -> Result<CleaningResult, CommandError> {
+    let app = app.clone();
+    cleaner::run_utils(mode, path, maya_exe, config, Some(&state.child), move |progress| {
+        let _ = app.emit("clean-progress", &progress);
+    })
+}
+
+// List prior cleaning sessions so the UI can show cleaning history.
+#[tauri::command]
+fn list_backups() -> Result<Vec<BackupEntry>, CommandError> {
+    backup::list_backups()
+}
+
+// Roll a single cleaned file back to its pre-clean state.
+#[tauri::command]
+fn restore_backup(entry_id: String) -> Result<(), CommandError> {
+    backup::restore_backup(&entry_id)
+}
+
+// Kill an in-flight clean started by one of the `clean_*` commands, if any.
+#[tauri::command]
+fn cancel_cleaning(state: State<AppState>) -> Result<(), CommandError> {
+    state.child.cancel()
 }
 
 // Clean a Maya scene file
 #[tauri::command]
-fn clean_maya_scene(file_path: String, state: State<AppState>) -> Result<CleaningResult, String> {
+fn clean_maya_scene(file_path: String, app: AppHandle, state: State<AppState>) -> Result<CleaningResult, CommandError> {
     println!("Called clean_maya_scene with path: {}", file_path);
-    
+
     // Validate the path exists
     let path = Path::new(&file_path);
     if !path.exists() {
-        return Err(format!("File not found: {}. Please check if the file exists and you have permission to access it.", file_path));
+        return Err(CommandError::InvalidPath(file_path));
     }
-    
+
     // Check if it's a Maya file
     let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
     if ext.to_lowercase() != "ma" && ext.to_lowercase() != "mb" {
-        return Err(format!("File is not a Maya file (.ma or .mb): {}", file_path));
+        return Err(CommandError::NotAMayaFile(file_path));
     }
-    
+
     println!("File exists at: {}", file_path);
-    let maya_exe = find_maya_exe(state)?;
-    run_utils("scene", Some(&file_path), &maya_exe)
+    let config = state.config.lock().unwrap().clone();
+    let maya_exe = find_maya_exe(state)?.path;
+    run_utils("scene", Some(&file_path), &maya_exe, &config, &app, &state)
 }
 
 // Clean a directory of Maya files
 #[tauri::command]
-fn clean_maya_directory(dir_path: String, state: State<AppState>) -> Result<CleaningResult, String> {
+fn clean_maya_directory(dir_path: String, app: AppHandle, state: State<AppState>) -> Result<CleaningResult, CommandError> {
     // Check if directory exists
     let path = Path::new(&dir_path);
     if !path.exists() || !path.is_dir() {
-        return Err(format!("Directory not found: {}", dir_path));
+        return Err(CommandError::InvalidPath(dir_path));
     }
-    
-    let maya_exe = find_maya_exe(state)?;
-    run_utils("directory", Some(&dir_path), &maya_exe)
+
+    let config = state.config.lock().unwrap().clone();
+    let maya_exe = find_maya_exe(state)?.path;
+    run_utils("directory", Some(&dir_path), &maya_exe, &config, &app, &state)
 }
 
 // Clean Maya user directories
 #[tauri::command]
-fn clean_maya_user_dirs(state: State<AppState>) -> Result<CleaningResult, String> {
-    let maya_exe = find_maya_exe(state)?;
-    run_utils("user", None, &maya_exe)
+fn clean_maya_user_dirs(app: AppHandle, state: State<AppState>) -> Result<CleaningResult, CommandError> {
+    let config = state.config.lock().unwrap().clone();
+    let maya_exe = find_maya_exe(state)?.path;
+    run_utils("user", None, &maya_exe, &config, &app, &state)
+}
+
+// Report what cleaning a scene file would do, without changing it
+#[tauri::command]
+fn preview_maya_scene(file_path: String, app: AppHandle, state: State<AppState>) -> Result<CleaningResult, CommandError> {
+    let path = Path::new(&file_path);
+    if !path.exists() {
+        return Err(CommandError::InvalidPath(file_path));
+    }
+
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    if ext.to_lowercase() != "ma" && ext.to_lowercase() != "mb" {
+        return Err(CommandError::NotAMayaFile(file_path));
+    }
+
+    let config = state.config.lock().unwrap().clone();
+    let maya_exe = find_maya_exe(state)?.path;
+    run_utils("preview", Some(&file_path), &maya_exe, &config, &app, &state)
+}
+
+// Report what cleaning a directory would do, without changing anything in it
+#[tauri::command]
+fn preview_maya_directory(dir_path: String, app: AppHandle, state: State<AppState>) -> Result<CleaningResult, CommandError> {
+    let path = Path::new(&dir_path);
+    if !path.exists() || !path.is_dir() {
+        return Err(CommandError::InvalidPath(dir_path));
+    }
+
+    let config = state.config.lock().unwrap().clone();
+    let maya_exe = find_maya_exe(state)?.path;
+    run_utils("preview", Some(&dir_path), &maya_exe, &config, &app, &state)
 }
\ No newline at end of file